@@ -1,14 +1,23 @@
 use crate::io::Text;
 use crate::{Metrics, Error};
 use crate::task::Join;
-use futures_io::AsyncRead;
+use bytes::Bytes;
+use futures_io::{AsyncRead, AsyncWrite};
 use futures_util::AsyncReadExt;
 use http::{Response, Uri};
 use std::fs::File;
 use std::io::{self, Read, Write};
 use std::path::Path;
+#[cfg(feature = "decompression")]
+use std::pin::Pin;
+#[cfg(feature = "decompression")]
+use std::task::{Context, Poll};
 
 type TextFuture<'a> = futures_util::future::LocalBoxFuture<'a, Result<String, Error>>;
+type ChunksStream<'a> = futures_util::stream::LocalBoxStream<'a, Result<Bytes, Error>>;
+type CopyFuture<'a> = futures_util::future::LocalBoxFuture<'a, io::Result<u64>>;
+#[cfg(feature = "json")]
+type JsonFuture<'a, D> = futures_util::future::LocalBoxFuture<'a, Result<D, serde_json::Error>>;
 
 /// Provides extension methods for working with HTTP responses.
 pub trait ResponseExt<T> {
@@ -30,6 +39,50 @@ pub trait ResponseExt<T> {
     /// [`HttpClientBuilder::metrics`](crate::HttpClientBuilder::metrics).
     fn metrics(&self) -> Option<&Metrics>;
 
+    /// Get the content type of this response, as specified by the
+    /// `Content-Type` header.
+    ///
+    /// Returns `None` if the response did not include a `Content-Type`
+    /// header, or if its value could not be parsed as a valid media type.
+    fn content_type(&self) -> Option<mime::Mime>;
+
+    /// Get the content length of this response, as specified by the
+    /// `Content-Length` header.
+    ///
+    /// Note that the value reported by this method may not match the actual
+    /// number of bytes in the response body, as it is determined solely by
+    /// the `Content-Length` header, which the server is not obligated to
+    /// provide accurately, and which may be absent entirely (for example,
+    /// with chunked transfer encoding).
+    fn content_length(&self) -> Option<u64>;
+
+    /// Get the response body, transparently decompressed according to its
+    /// `Content-Encoding` header.
+    ///
+    /// Supports `gzip`, `deflate`, and `br` (Brotli), decoded
+    /// case-insensitively. Stacked encodings (e.g. `Content-Encoding: gzip,
+    /// br`) are undone in reverse order. The body is passed through
+    /// unchanged if the header is absent, `identity`, or names an unknown
+    /// encoding.
+    ///
+    /// This is only useful if automatic decompression has been disabled on
+    /// the client, since by default curl already decodes the body for you.
+    /// This method requires the `decompression` feature to be enabled.
+    #[cfg(feature = "decompression")]
+    fn decompressed(&mut self) -> Decompress<'_>
+    where
+        T: Read;
+
+    /// Get the response body, transparently decompressed according to its
+    /// `Content-Encoding` header, asynchronously.
+    ///
+    /// See [`decompressed`](ResponseExt::decompressed) for details. This
+    /// method requires the `decompression` feature to be enabled.
+    #[cfg(feature = "decompression")]
+    fn decompressed_async(&mut self) -> DecompressAsync<'_>
+    where
+        T: AsyncRead + Unpin;
+
     /// Copy the response body into a writer.
     ///
     /// Returns the number of bytes that were written.
@@ -37,6 +90,38 @@ pub trait ResponseExt<T> {
     where
         T: Read;
 
+    /// Get the response body as a stream of chunks.
+    ///
+    /// Unlike [`text`](ResponseExt::text), this does not buffer the entire
+    /// response body into memory. Instead,
+    /// each item produced is a freshly-allocated [`Bytes`] containing
+    /// whatever was read from the underlying body in that read, allowing
+    /// arbitrarily large responses to be processed incrementally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use isahc::prelude::*;
+    ///
+    /// let mut response = isahc::get("https://example.org")?;
+    ///
+    /// for chunk in response.chunks() {
+    ///     let chunk = chunk?;
+    ///     println!("got {} bytes", chunk.len());
+    /// }
+    /// # Ok::<(), isahc::Error>(())
+    /// ```
+    fn chunks(&mut self) -> Chunks<'_, T>
+    where
+        T: Read;
+
+    /// Get the response body as an asynchronous stream of chunks.
+    ///
+    /// This is the asynchronous equivalent of [`chunks`](ResponseExt::chunks).
+    fn chunks_stream(&mut self) -> ChunksStream<'_>
+    where
+        T: AsyncRead + Unpin;
+
     /// Write the response body to a file.
     ///
     /// This method makes it convenient to download a file using a GET request
@@ -60,6 +145,28 @@ pub trait ResponseExt<T> {
         File::create(path).and_then(|f| self.copy_to(f))
     }
 
+    /// Copy the response body into a writer asynchronously.
+    ///
+    /// Returns the number of bytes that were written.
+    fn copy_to_async<'a>(&'a mut self, writer: impl AsyncWrite + Unpin + 'a) -> CopyFuture<'a>
+    where
+        T: AsyncRead + Unpin;
+
+    /// Write the response body to a file asynchronously.
+    ///
+    /// This method makes it convenient to download a file using a GET request
+    /// and write it to a file in a single chain of calls.
+    ///
+    /// Returns the number of bytes that were written.
+    ///
+    /// Opening the file and every write to it are performed with blocking
+    /// standard library I/O under the hood, so despite returning a future,
+    /// this method is not suitable for use on a single-threaded executor or
+    /// anywhere else a blocked thread would stall other tasks.
+    fn copy_to_file_async(&mut self, path: impl AsRef<Path>) -> CopyFuture<'_>
+    where
+        T: AsyncRead + Unpin;
+
     /// Get the response body as a string.
     ///
     /// This method consumes the entire response body stream and can only be
@@ -86,6 +193,27 @@ pub trait ResponseExt<T> {
     where
         T: AsyncRead + Unpin;
 
+    /// Get the response body as a string, decoded using a specific charset
+    /// rather than the one suggested by the `Content-Type` header or a BOM.
+    ///
+    /// This is useful for servers that send the wrong charset label, or none
+    /// at all. This method requires the `encoding` feature to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use isahc::prelude::*;
+    ///
+    /// let text = isahc::get("https://example.org")?
+    ///     .text_with_encoding(encoding_rs::WINDOWS_1252)?;
+    /// println!("{}", text);
+    /// # Ok::<(), isahc::Error>(())
+    /// ```
+    #[cfg(feature = "encoding")]
+    fn text_with_encoding(&mut self, encoding: &'static encoding_rs::Encoding) -> Result<String, Error>
+    where
+        T: Read;
+
     /// Deserialize the response body as JSON into a given type.
     ///
     /// This method requires the `json` feature to be enabled.
@@ -105,6 +233,15 @@ pub trait ResponseExt<T> {
     where
         D: serde::de::DeserializeOwned,
         T: Read;
+
+    /// Deserialize the response body as JSON into a given type, asynchronously.
+    ///
+    /// This method requires the `json` feature to be enabled.
+    #[cfg(feature = "json")]
+    fn json_async<D>(&mut self) -> JsonFuture<'_, D>
+    where
+        D: serde::de::DeserializeOwned,
+        T: AsyncRead + Unpin;
 }
 
 impl<T> ResponseExt<T> for Response<T> {
@@ -116,6 +253,70 @@ impl<T> ResponseExt<T> for Response<T> {
         self.extensions().get()
     }
 
+    fn content_type(&self) -> Option<mime::Mime> {
+        self.headers()
+            .get(http::header::CONTENT_TYPE)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn content_length(&self) -> Option<u64> {
+        self.headers()
+            .get(http::header::CONTENT_LENGTH)?
+            .to_str()
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    #[cfg(feature = "decompression")]
+    fn decompressed(&mut self) -> Decompress<'_>
+    where
+        T: Read,
+    {
+        let encodings = content_encodings(self.headers());
+        let mut reader: Box<dyn Read + '_> = Box::new(self.body_mut());
+
+        for encoding in encodings.iter().rev() {
+            reader = match encoding.as_str() {
+                "gzip" | "x-gzip" => Box::new(flate2::read::GzDecoder::new(reader)),
+                "deflate" => Box::new(flate2::read::DeflateDecoder::new(reader)),
+                "br" => Box::new(brotli::Decompressor::new(reader, 8192)),
+                _ => reader,
+            };
+        }
+
+        Decompress(reader)
+    }
+
+    #[cfg(feature = "decompression")]
+    fn decompressed_async(&mut self) -> DecompressAsync<'_>
+    where
+        T: AsyncRead + Unpin,
+    {
+        let encodings = content_encodings(self.headers());
+        let mut reader: Pin<Box<dyn AsyncRead + '_>> = Box::pin(self.body_mut());
+
+        for encoding in encodings.iter().rev() {
+            reader = match encoding.as_str() {
+                "gzip" | "x-gzip" => Box::pin(async_compression::futures::bufread::GzipDecoder::new(
+                    futures_util::io::BufReader::new(reader),
+                )),
+                "deflate" => Box::pin(async_compression::futures::bufread::DeflateDecoder::new(
+                    futures_util::io::BufReader::new(reader),
+                )),
+                "br" => Box::pin(async_compression::futures::bufread::BrotliDecoder::new(
+                    futures_util::io::BufReader::new(reader),
+                )),
+                _ => reader,
+            };
+        }
+
+        DecompressAsync(reader)
+    }
+
     fn copy_to(&mut self, mut writer: impl Write) -> io::Result<u64>
     where
         T: Read,
@@ -123,37 +324,62 @@ impl<T> ResponseExt<T> for Response<T> {
         io::copy(self.body_mut(), &mut writer)
     }
 
-    #[cfg(feature = "encoding")]
-    fn text(&mut self) -> Result<String, Error>
+    fn copy_to_async<'a>(&'a mut self, mut writer: impl AsyncWrite + Unpin + 'a) -> CopyFuture<'a>
     where
-        T: Read,
+        T: AsyncRead + Unpin,
     {
-        let encoding = get_encoding(self).unwrap();
-        let mut decoder = encoding.new_decoder();
-        let mut string = String::new();
+        Box::pin(async move { futures_util::io::copy(self.body_mut(), &mut writer).await })
+    }
 
-        let mut buf = [0; 8192];
+    fn copy_to_file_async(&mut self, path: impl AsRef<Path>) -> CopyFuture<'_>
+    where
+        T: AsyncRead + Unpin,
+    {
+        let path = path.as_ref().to_owned();
 
-        loop {
-            let len = match self.body_mut().read(&mut buf) {
-                Ok(0) => break,
-                Ok(len) => len,
-                Err(e) => if e.kind() == io::ErrorKind::Interrupted {
-                    continue
-                } else {
-                    return Err(e.into())
-                },
-            };
+        Box::pin(async move {
+            let file = File::create(path)?;
+            let mut file = futures_util::io::AllowStdIo::new(file);
 
-            match decoder.decode_to_string(&buf[..len], &mut string, false) {
-                (encoding_rs::CoderResult::InputEmpty, _, _) => {
+            futures_util::io::copy(self.body_mut(), &mut file).await
+        })
+    }
 
-                }
-                _ => {}
-            }
+    fn chunks(&mut self) -> Chunks<'_, T>
+    where
+        T: Read,
+    {
+        Chunks {
+            body: self.body_mut(),
         }
+    }
 
-        Ok(string)
+    fn chunks_stream(&mut self) -> ChunksStream<'_>
+    where
+        T: AsyncRead + Unpin,
+    {
+        Box::pin(futures_util::stream::unfold(self.body_mut(), |body| async move {
+            let mut buf = [0; 8192];
+
+            loop {
+                return match body.read(&mut buf).await {
+                    Ok(0) => None,
+                    Ok(len) => Some((Ok(Bytes::copy_from_slice(&buf[..len])), body)),
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => Some((Err(e.into()), body)),
+                };
+            }
+        }))
+    }
+
+    #[cfg(feature = "encoding")]
+    fn text(&mut self) -> Result<String, Error>
+    where
+        T: Read,
+    {
+        let encoding = get_encoding(self);
+
+        decode(self.body_mut(), encoding, true)
     }
 
     #[cfg(not(feature = "encoding"))]
@@ -167,40 +393,37 @@ impl<T> ResponseExt<T> for Response<T> {
         Ok(string)
     }
 
+    #[cfg(feature = "encoding")]
     fn text_async(&mut self) -> TextFuture<'_>
     where
         T: AsyncRead + Unpin,
     {
-        Box::pin(async move {
-            let encoding = get_encoding(self).unwrap();
-            let mut decoder = encoding.new_decoder();
-            let mut string = String::new();
-
-            let mut buf = [0; 8192];
+        let encoding = get_encoding(self);
 
-            loop {
-                let len = match self.body_mut().read(&mut buf).await {
-                    Ok(0) => break,
-                    Ok(len) => len,
-                    Err(e) => if e.kind() == io::ErrorKind::Interrupted {
-                        continue
-                    } else {
-                        return Err(e.into())
-                    },
-                };
-
-                match decoder.decode_to_string(&buf[..len], &mut string, false) {
-                    (encoding_rs::CoderResult::InputEmpty, _, _) => {
+        Box::pin(decode_async(self.body_mut(), encoding, true))
+    }
 
-                    }
-                    _ => {}
-                }
-            }
+    #[cfg(not(feature = "encoding"))]
+    fn text_async(&mut self) -> TextFuture<'_>
+    where
+        T: AsyncRead + Unpin,
+    {
+        Box::pin(async move {
+            let mut string = String::new();
+            self.body_mut().read_to_string(&mut string).await?;
 
             Ok(string)
         })
     }
 
+    #[cfg(feature = "encoding")]
+    fn text_with_encoding(&mut self, encoding: &'static encoding_rs::Encoding) -> Result<String, Error>
+    where
+        T: Read,
+    {
+        decode(self.body_mut(), encoding, false)
+    }
+
     #[cfg(feature = "json")]
     fn json<D>(&mut self) -> Result<D, serde_json::Error>
     where
@@ -209,32 +432,442 @@ impl<T> ResponseExt<T> for Response<T> {
     {
         serde_json::from_reader(self.body_mut())
     }
+
+    #[cfg(feature = "json")]
+    fn json_async<D>(&mut self) -> JsonFuture<'_, D>
+    where
+        D: serde::de::DeserializeOwned,
+        T: AsyncRead + Unpin,
+    {
+        Box::pin(async move {
+            let mut buf = Vec::new();
+            self.body_mut()
+                .read_to_end(&mut buf)
+                .await
+                .map_err(serde_json::Error::io)?;
+
+            serde_json::from_slice(&buf)
+        })
+    }
 }
 
 pub(crate) struct EffectiveUri(pub(crate) Uri);
 
-fn get_encoding<T>(response: &http::Response<T>) -> Option<encoding_rs::Encoding> {
-    let content_type = response.headers().get(http::header::CONTENT_TYPE)?;
+// NOT IMPLEMENTED: exposing HTTP trailers (headers sent after a chunked
+// body, e.g. `Grpc-Status` or a checksum) via `ResponseExt::trailers()`.
+//
+// Doing so needs the client layer to capture the trailer header block once
+// the transfer completes and stash it in the response extensions here,
+// alongside `EffectiveUri` and `Metrics` above — that capture point does not
+// exist anywhere in this crate yet, so there is nothing for an accessor to
+// read. Land the client-side capture before adding the accessor.
+
+/// A response body being transparently decompressed, returned by
+/// [`ResponseExt::decompressed`].
+#[cfg(feature = "decompression")]
+pub struct Decompress<'a>(Box<dyn Read + 'a>);
+
+#[cfg(feature = "decompression")]
+impl<'a> Read for Decompress<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// A response body being transparently decompressed asynchronously,
+/// returned by [`ResponseExt::decompressed_async`].
+#[cfg(feature = "decompression")]
+pub struct DecompressAsync<'a>(Pin<Box<dyn AsyncRead + 'a>>);
+
+#[cfg(feature = "decompression")]
+impl<'a> AsyncRead for DecompressAsync<'a> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.as_mut().poll_read(cx, buf)
+    }
+}
+
+/// Parse the `Content-Encoding` header into a list of lowercase encoding
+/// names, in the order they were applied.
+#[cfg(feature = "decompression")]
+fn content_encodings(headers: &http::HeaderMap) -> Vec<String> {
+    headers
+        .get(http::header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| {
+            value
+                .split(',')
+                .map(|encoding| encoding.trim().to_ascii_lowercase())
+                .filter(|encoding| !encoding.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// An iterator over the chunks of a response body, returned by
+/// [`ResponseExt::chunks`].
+pub struct Chunks<'a, T> {
+    body: &'a mut T,
+}
+
+impl<'a, T: Read> Iterator for Chunks<'a, T> {
+    type Item = Result<Bytes, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = [0; 8192];
+
+        loop {
+            return match self.body.read(&mut buf) {
+                Ok(0) => None,
+                Ok(len) => Some(Ok(Bytes::copy_from_slice(&buf[..len]))),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => Some(Err(e.into())),
+            };
+        }
+    }
+}
+
+/// Determine the charset to decode a response's body with, based on the
+/// `charset` parameter of its `Content-Type` header, falling back to UTF-8
+/// if the header is missing, unparsable, or names an unknown charset.
+#[cfg(feature = "encoding")]
+fn get_encoding<T>(response: &http::Response<T>) -> &'static encoding_rs::Encoding {
+    if response.headers().contains_key(http::header::CONTENT_TYPE) {
+        let content_type = match response.content_type() {
+            Some(content_type) => content_type,
+            None => {
+                log::warn!("could not parse Content-Type header");
+                return encoding_rs::UTF_8;
+            }
+        };
+
+        if let Some(charset) = content_type.get_param("charset") {
+            if let Some(encoding) = encoding_rs::Encoding::for_label(charset.as_str().as_bytes()) {
+                return encoding;
+            }
+        }
+    }
+
+    encoding_rs::UTF_8
+}
+
+/// Sniff a byte-order mark at the start of a response body, as recommended
+/// by the Encoding Standard for web content. Returns the encoding it
+/// indicates along with the number of bytes the BOM itself occupies.
+#[cfg(feature = "encoding")]
+fn detect_bom(buf: &[u8]) -> Option<(&'static encoding_rs::Encoding, usize)> {
+    if buf.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        Some((encoding_rs::UTF_8, 3))
+    } else if buf.starts_with(&[0xFF, 0xFE]) {
+        Some((encoding_rs::UTF_16LE, 2))
+    } else if buf.starts_with(&[0xFE, 0xFF]) {
+        Some((encoding_rs::UTF_16BE, 2))
+    } else {
+        None
+    }
+}
+
+/// Feed a chunk of bytes through a decoder into `string`, growing `string`'s
+/// spare capacity and re-feeding the unconsumed remainder until the decoder
+/// reports that it has consumed all of `chunk`.
+///
+/// `decode_to_string` only ever writes into the destination string's
+/// existing spare capacity, returning `CoderResult::OutputFull` (having
+/// written and consumed nothing) if there isn't enough of it; a fresh
+/// `String` has zero capacity, so this reserves space up front and retries
+/// as needed rather than silently dropping input.
+#[cfg(feature = "encoding")]
+fn feed_decoder(
+    decoder: &mut encoding_rs::Decoder,
+    mut chunk: &[u8],
+    string: &mut String,
+    last: bool,
+) {
+    loop {
+        let needed = decoder
+            .max_utf8_buffer_length(chunk.len())
+            .unwrap_or_else(|| chunk.len());
+        string.reserve(needed);
+
+        let (result, consumed, _) = decoder.decode_to_string(chunk, string, last);
+        chunk = &chunk[consumed..];
+
+        if result == encoding_rs::CoderResult::InputEmpty {
+            break;
+        }
+    }
+}
+
+/// The longest byte-order mark we sniff for (the 3-byte UTF-8 BOM).
+#[cfg(feature = "encoding")]
+const BOM_MAX_LEN: usize = 3;
+
+/// Read from `body` until at least [`BOM_MAX_LEN`] bytes have been
+/// accumulated or the stream ends, so that a BOM split across multiple short
+/// reads (as can happen on a slow or chunked stream) is never mistaken for
+/// plain content.
+#[cfg(feature = "encoding")]
+fn read_bom_prefix(mut body: impl Read, buf: &mut [u8; 8192]) -> io::Result<usize> {
+    let mut len = 0;
+
+    while len < BOM_MAX_LEN {
+        match body.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len)
+}
+
+/// Decode a response body with a given encoding, optionally overriding that
+/// encoding with one sniffed from a leading byte-order mark.
+#[cfg(feature = "encoding")]
+fn decode(
+    mut body: impl Read,
+    mut encoding: &'static encoding_rs::Encoding,
+    sniff_bom: bool,
+) -> Result<String, Error> {
+    let mut string = String::new();
+    let mut buf = [0; 8192];
+
+    let mut prefix_len = 0;
+
+    if sniff_bom {
+        prefix_len = read_bom_prefix(&mut body, &mut buf)?;
 
-    let content_type = match content_type.to_str() {
-        Ok(s) => s,
-        Err(e) => {
-            log::warn!("could not parse Content-Type header: {}", e);
-            return None;
+        if let Some((bom_encoding, bom_len)) = detect_bom(&buf[..prefix_len]) {
+            encoding = bom_encoding;
+            buf.copy_within(bom_len..prefix_len, 0);
+            prefix_len -= bom_len;
         }
-    };
+    }
+
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    feed_decoder(&mut decoder, &buf[..prefix_len], &mut string, false);
+
+    loop {
+        let len = match body.read(&mut buf) {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        feed_decoder(&mut decoder, &buf[..len], &mut string, false);
+    }
+
+    feed_decoder(&mut decoder, &[], &mut string, true);
+
+    Ok(string)
+}
+
+/// Asynchronous counterpart to [`read_bom_prefix`].
+#[cfg(feature = "encoding")]
+async fn read_bom_prefix_async(
+    mut body: impl AsyncRead + Unpin,
+    buf: &mut [u8; 8192],
+) -> io::Result<usize> {
+    let mut len = 0;
+
+    while len < BOM_MAX_LEN {
+        match body.read(&mut buf[len..]).await {
+            Ok(0) => break,
+            Ok(read) => len += read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(len)
+}
+
+/// Asynchronous counterpart to [`decode`].
+#[cfg(feature = "encoding")]
+async fn decode_async(
+    mut body: impl AsyncRead + Unpin,
+    mut encoding: &'static encoding_rs::Encoding,
+    sniff_bom: bool,
+) -> Result<String, Error> {
+    let mut string = String::new();
+    let mut buf = [0; 8192];
+
+    let mut prefix_len = 0;
+
+    if sniff_bom {
+        prefix_len = read_bom_prefix_async(&mut body, &mut buf).await?;
+
+        if let Some((bom_encoding, bom_len)) = detect_bom(&buf[..prefix_len]) {
+            encoding = bom_encoding;
+            buf.copy_within(bom_len..prefix_len, 0);
+            prefix_len -= bom_len;
+        }
+    }
+
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    feed_decoder(&mut decoder, &buf[..prefix_len], &mut string, false);
+
+    loop {
+        let len = match body.read(&mut buf).await {
+            Ok(0) => break,
+            Ok(len) => len,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        feed_decoder(&mut decoder, &buf[..len], &mut string, false);
+    }
+
+    feed_decoder(&mut decoder, &[], &mut string, true);
+
+    Ok(string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn text_round_trips_utf8_body() {
+        let mut response = http::Response::new(Cursor::new(b"hello world".to_vec()));
+
+        assert_eq!(response.text().unwrap(), "hello world");
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn text_strips_utf8_bom() {
+        let mut body = vec![0xEF, 0xBB, 0xBF];
+        body.extend_from_slice(b"hello world");
+        let mut response = http::Response::new(Cursor::new(body));
+
+        assert_eq!(response.text().unwrap(), "hello world");
+    }
+
+    /// A reader that only ever returns a single byte per `read` call, used to
+    /// exercise code that must not assume values spanning several bytes (a
+    /// BOM, a chunk boundary) arrive in a single read.
+    struct OneByteAtATime<R>(R);
 
-    let content_type = match content_type.parse::<mime::Mime>() {
-        Ok(s) => s,
-        Err(e) => {
-            log::warn!("could not parse Content-Type header: {}", e);
-            return None;
+    impl<R: Read> Read for OneByteAtATime<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.0.read(&mut buf[..buf.len().min(1)])
         }
-    };
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn text_strips_utf8_bom_split_across_reads() {
+        let mut body = vec![0xEF, 0xBB, 0xBF];
+        body.extend_from_slice(b"hello world");
+        let mut response = http::Response::new(OneByteAtATime(Cursor::new(body)));
+
+        assert_eq!(response.text().unwrap(), "hello world");
+    }
+
+    #[test]
+    fn chunks_round_trip_body() {
+        let mut response = http::Response::new(Cursor::new(b"hello world".to_vec()));
+
+        let collected: Vec<u8> = response
+            .chunks()
+            .flat_map(|chunk| chunk.unwrap().to_vec())
+            .collect();
+
+        assert_eq!(collected, b"hello world");
+    }
+
+    #[test]
+    fn chunks_round_trip_body_across_multiple_chunks() {
+        let mut response =
+            http::Response::new(OneByteAtATime(Cursor::new(b"hello world".to_vec())));
+
+        let chunks: Vec<Bytes> = response.chunks().map(|chunk| chunk.unwrap()).collect();
+        assert_eq!(chunks.len(), "hello world".len());
+
+        let collected: Vec<u8> = chunks.into_iter().flat_map(|chunk| chunk.to_vec()).collect();
+        assert_eq!(collected, b"hello world");
+    }
+
+    #[cfg(feature = "decompression")]
+    fn gzip(plaintext: &[u8]) -> Vec<u8> {
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(plaintext).unwrap();
+        encoder.finish().unwrap()
+    }
 
-    .and_then(|mime| mime.get_param("charset"))
-    .map(|charset| charset.as_str().as_bytes())
-    .and_then(encoding_rs::Encoding::for_label)
-    .unwrap_or(encoding_rs::UTF_8);
-    None
+    #[cfg(feature = "decompression")]
+    fn deflate(plaintext: &[u8]) -> Vec<u8> {
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(plaintext).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[cfg(feature = "decompression")]
+    fn brotli(plaintext: &[u8]) -> Vec<u8> {
+        let mut compressed = Vec::new();
+        let mut encoder = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+        encoder.write_all(plaintext).unwrap();
+        drop(encoder);
+        compressed
+    }
+
+    #[cfg(feature = "decompression")]
+    fn decompressed_response(encoding: &str, body: Vec<u8>) -> http::Response<Cursor<Vec<u8>>> {
+        http::Response::builder()
+            .header("content-encoding", encoding)
+            .body(Cursor::new(body))
+            .unwrap()
+    }
+
+    #[cfg(feature = "decompression")]
+    #[test]
+    fn decompressed_round_trips_gzip_body() {
+        let mut response = decompressed_response("gzip", gzip(b"hello world"));
+        let mut decoded = String::new();
+        response.decompressed().read_to_string(&mut decoded).unwrap();
+
+        assert_eq!(decoded, "hello world");
+    }
+
+    #[cfg(feature = "decompression")]
+    #[test]
+    fn decompressed_round_trips_deflate_body() {
+        let mut response = decompressed_response("deflate", deflate(b"hello world"));
+        let mut decoded = String::new();
+        response.decompressed().read_to_string(&mut decoded).unwrap();
+
+        assert_eq!(decoded, "hello world");
+    }
+
+    #[cfg(feature = "decompression")]
+    #[test]
+    fn decompressed_round_trips_brotli_body() {
+        let mut response = decompressed_response("br", brotli(b"hello world"));
+        let mut decoded = String::new();
+        response.decompressed().read_to_string(&mut decoded).unwrap();
+
+        assert_eq!(decoded, "hello world");
+    }
+
+    #[cfg(feature = "decompression")]
+    #[test]
+    fn decompressed_undoes_stacked_encodings_in_reverse_order() {
+        // `Content-Encoding: gzip, br` means gzip was applied first and then
+        // brotli on top, so decoding must undo brotli before gzip.
+        let body = brotli(&gzip(b"hello world"));
+        let mut response = decompressed_response("gzip, br", body);
+        let mut decoded = String::new();
+        response.decompressed().read_to_string(&mut decoded).unwrap();
+
+        assert_eq!(decoded, "hello world");
+    }
 }